@@ -0,0 +1,9 @@
+//! A nom-based parser, serializer and Tokio codec for the Redis Serialization
+//! Protocol (RESP), covering both RESP2 and RESP3.
+
+// The parsers follow nom's convention of eliding the input lifetime in their
+// return type (e.g. `fn(i: &str) -> IResult<&str, RespValue>`); allow the
+// resulting elided-vs-named mismatch rather than annotating every signature.
+#![allow(mismatched_lifetime_syntaxes)]
+
+pub mod resp;
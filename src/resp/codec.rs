@@ -0,0 +1,140 @@
+//! A [`tokio_util::codec`] implementation for framed RESP connections.
+//!
+//! Gated behind the `codec` feature, since it pulls in `tokio-util` and
+//! `bytes`. With it enabled a live Redis connection becomes simply:
+//!
+//! ```ignore
+//! let framed = Framed::new(tcp_stream, RespCodec);
+//! ```
+//!
+//! The decoder runs the [`streaming`](crate::resp::streaming) parser against
+//! the buffered bytes: a truncated frame surfaces as `Incomplete` and is left
+//! untouched for the next poll, while a complete frame is parsed and the exact
+//! number of consumed bytes is drained from the `BytesMut`.
+
+use bytes::Buf;
+use bytes::BytesMut;
+use tokio_util::codec::Decoder;
+use tokio_util::codec::Encoder;
+
+use crate::resp::RespValue;
+use crate::resp::RespValueBytes;
+use crate::resp::streaming;
+
+/// An owned RESP value yielded by the [`RespCodec`] decoder.
+///
+/// The zero-copy [`RespValueBytes`] borrows from the buffer it was parsed out
+/// of, which a `Decoder` cannot return — its `Item` must outlive the
+/// `BytesMut` — so the decoder hands back this owned mirror instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RespValueOwned {
+    SimpleString(String),
+    Error(String),
+    Integer(i64),
+    BulkString(Option<Vec<u8>>),
+    Array(Option<Vec<RespValueOwned>>),
+}
+
+impl From<RespValueBytes<'_>> for RespValueOwned {
+    fn from(value: RespValueBytes<'_>) -> Self {
+        match value {
+            RespValueBytes::SimpleString(s) => RespValueOwned::SimpleString(s.to_owned()),
+            RespValueBytes::Error(s) => RespValueOwned::Error(s.to_owned()),
+            RespValueBytes::Integer(n) => RespValueOwned::Integer(n),
+            RespValueBytes::BulkString(b) => RespValueOwned::BulkString(b.map(<[u8]>::to_vec)),
+            RespValueBytes::Array(a) => {
+                RespValueOwned::Array(a.map(|items| items.into_iter().map(Into::into).collect()))
+            }
+        }
+    }
+}
+
+/// Encodes [`RespValue`]s onto, and decodes [`RespValueOwned`]s off, a framed
+/// byte stream.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RespCodec;
+
+impl Decoder for RespCodec {
+    type Item = RespValueOwned;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match streaming::parse(src) {
+            Ok((rest, value)) => {
+                // Record how many bytes the frame occupied *before* mutating
+                // the buffer, then drain exactly that much.
+                let consumed = src.len() - rest.len();
+                let item = RespValueOwned::from(value);
+                src.advance(consumed);
+                Ok(Some(item))
+            }
+            // A partial frame simply means "need more data"; leave it buffered.
+            Err(nom::Err::Incomplete(_)) => Ok(None),
+            Err(e) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid RESP frame: {e:?}"),
+            )),
+        }
+    }
+}
+
+impl Encoder<RespValue<'_>> for RespCodec {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: RespValue<'_>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut buf = Vec::new();
+        item.encode_into(&mut buf);
+        dst.extend_from_slice(&buf);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_decode_advances_buffer() {
+    let mut buf = BytesMut::from(&b"+OK\r\n:1\r\n"[..]);
+    let mut codec = RespCodec;
+
+    assert_eq!(
+        codec.decode(&mut buf).unwrap(),
+        Some(RespValueOwned::SimpleString("OK".to_owned()))
+    );
+    // Only the first frame was consumed.
+    assert_eq!(&buf[..], b":1\r\n");
+    assert_eq!(
+        codec.decode(&mut buf).unwrap(),
+        Some(RespValueOwned::Integer(1))
+    );
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_decode_partial_frame_left_for_next_poll() {
+    let mut buf = BytesMut::from(&b"$6\r\nfoo"[..]);
+    let mut codec = RespCodec;
+
+    // Not enough bytes yet: nothing is decoded and the buffer is untouched.
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    assert_eq!(&buf[..], b"$6\r\nfoo");
+
+    buf.extend_from_slice(b"bar\r\n");
+    assert_eq!(
+        codec.decode(&mut buf).unwrap(),
+        Some(RespValueOwned::BulkString(Some(b"foobar".to_vec())))
+    );
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_encode_round_trips_through_decode() {
+    let mut buf = BytesMut::new();
+    let mut codec = RespCodec;
+
+    codec
+        .encode(RespValue::BulkString(Some("hello")), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..], b"$5\r\nhello\r\n");
+    assert_eq!(
+        codec.decode(&mut buf).unwrap(),
+        Some(RespValueOwned::BulkString(Some(b"hello".to_vec())))
+    );
+}
@@ -10,8 +10,14 @@ use nom::bytes::take_until;
 use nom::character::complete::crlf;
 use nom::combinator::complete;
 use nom::combinator::map;
+use nom::combinator::map_res;
 use nom::sequence::delimited;
 use nom::sequence::terminated;
+
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod streaming;
+
 pub const CRLF: &str = "\r\n";
 
 /// In RESP, the type of some data depends on the first byte:
@@ -21,13 +27,28 @@ pub const CRLF: &str = "\r\n";
 /// For Integers the first byte of the reply is ":"
 /// For Bulk Strings the first byte of the reply is "$"
 /// For Arrays the first byte of the reply is "*"
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// RESP3 (negotiated with `HELLO 3`) adds a handful of aggregate and scalar
+/// types on top of the five RESP2 ones, each keyed by its own first byte:
+/// "_" Null, "," Double, "#" Boolean, "(" Big Number, "!" Bulk Error,
+/// "=" Verbatim String, "%" Map, "~" Set and ">" Push.
+// `Eq` is dropped because `Double` holds an `f64`, which is only `PartialEq`.
+#[derive(Clone, Debug, PartialEq)]
 pub enum RespValue<'a> {
     SimpleString(&'a str),
     Error(&'a str),
     Integer(i64),
     BulkString(Option<&'a str>),
     Array(Option<Vec<RespValue<'a>>>),
+    Null,
+    Double(f64),
+    Boolean(bool),
+    BigNumber(&'a str),
+    BulkError(&'a str),
+    VerbatimString { format: &'a str, text: &'a str },
+    Map(Vec<(RespValue<'a>, RespValue<'a>)>),
+    Set(Vec<RespValue<'a>>),
+    Push(Vec<RespValue<'a>>),
 }
 
 ///
@@ -112,6 +133,15 @@ fn test_integer() {
     );
 }
 
+/// The nom failure returned when a length prefix is invalid: a negative length
+/// other than the `-1` sentinel, or a bulk payload above [`MAX_BULK_LENGTH`].
+///
+/// Keeping this in one place lets every length-prefixed combinator reject bad
+/// lengths up front, before any payload or element is read.
+pub(crate) fn length_error<I>(i: I) -> nom::Err<nom::error::Error<I>> {
+    nom::Err::Error(nom::error::Error::new(i, nom::error::ErrorKind::Verify))
+}
+
 /// RESP Bulk Strings
 ///
 /// Bulk Strings are used in order to represent a single binary safe string up to 512 MB in length.
@@ -127,6 +157,8 @@ pub fn bulk_string(i: &str) -> IResult<&str, RespValue> {
     let (i, len) = complete(delimited(tag("$"), isize, crlf)).parse(i)?;
     if len == -1 {
         Ok((i, RespValue::BulkString(None)))
+    } else if len < -1 || len as usize > MAX_BULK_LENGTH {
+        Err(length_error(i))
     } else {
         map(terminated(take(len as usize), crlf), |str| {
             RespValue::BulkString(Some(str))
@@ -146,6 +178,8 @@ fn test_bulk_string() {
     assert_eq!(bulk_string(i), Ok(("", RespValue::BulkString(Some("")))));
     let i = "$-1\r\n";
     assert_eq!(bulk_string(i), Ok(("", RespValue::BulkString(None))));
+    // A negative length other than -1 is rejected, not used as a capacity.
+    assert!(bulk_string("$-2\r\n").is_err());
 }
 
 /// RESP Arrays
@@ -163,7 +197,11 @@ pub fn array(i: &str) -> IResult<&str, RespValue> {
     if len == -1 {
         return Ok((i, RespValue::Array(None)));
     }
-    let mut vec = Vec::with_capacity(len as usize);
+    if len < -1 {
+        return Err(length_error(i));
+    }
+    // Grow lazily rather than reserving an attacker-controlled count up front.
+    let mut vec = Vec::new();
     let mut rset = i;
     for _ in 0..len {
         let (i, o) = alt((simple_string, error, integer, bulk_string, array)).parse(rset)?;
@@ -210,6 +248,10 @@ fn test_array() {
     let i = "*-1\r\n";
     assert_eq!(array(i), Ok(("", Array(None))));
 
+    // A negative length other than -1 is rejected rather than read as an
+    // empty Array.
+    assert!(array("*-2\r\n").is_err());
+
     let i = "*2\r\n*3\r\n:1\r\n:2\r\n:3\r\n*2\r\n+Foo\r\n-Bar\r\n";
     assert_eq!(
         array(i),
@@ -236,3 +278,1003 @@ fn test_array() {
         ))
     );
 }
+
+/// RESP3 Null
+///
+/// The RESP3 protocol replaces the RESP2 null bulk string (`$-1\r\n`) and null
+/// array (`*-1\r\n`) with a single dedicated type: the null byte "_" followed
+/// by CRLF.
+pub fn null(i: &str) -> IResult<&str, RespValue> {
+    map(complete(terminated(tag("_"), crlf)), |_| RespValue::Null).parse(i)
+}
+
+#[test]
+fn test_null() {
+    let i = "_\r\n";
+    assert_eq!(null(i), Ok(("", RespValue::Null)));
+}
+
+/// RESP3 Doubles
+///
+/// A comma byte "," followed by the textual representation of a double
+/// precision floating point number, terminated by CRLF. The three special
+/// values `inf`, `-inf` and `nan` are spelled out rather than encoded.
+pub fn double(i: &str) -> IResult<&str, RespValue> {
+    let (rest, s) = complete(delimited(tag(","), take_until(CRLF), crlf)).parse(i)?;
+    let value = match s {
+        "inf" => f64::INFINITY,
+        "-inf" => f64::NEG_INFINITY,
+        "nan" => f64::NAN,
+        _ => s.parse::<f64>().map_err(|_| {
+            nom::Err::Error(nom::error::Error::new(i, nom::error::ErrorKind::Float))
+        })?,
+    };
+    Ok((rest, RespValue::Double(value)))
+}
+
+#[test]
+fn test_double() {
+    assert_eq!(double(",1.5\r\n"), Ok(("", RespValue::Double(1.5))));
+    assert_eq!(double(",10\r\n"), Ok(("", RespValue::Double(10.0))));
+    assert_eq!(double(",inf\r\n"), Ok(("", RespValue::Double(f64::INFINITY))));
+    assert_eq!(
+        double(",-inf\r\n"),
+        Ok(("", RespValue::Double(f64::NEG_INFINITY)))
+    );
+    match double(",nan\r\n") {
+        Ok(("", RespValue::Double(d))) => assert!(d.is_nan()),
+        other => panic!("unexpected {other:?}"),
+    }
+}
+
+/// RESP3 Booleans
+///
+/// A "#" byte followed by "t" for true or "f" for false, terminated by CRLF.
+pub fn boolean(i: &str) -> IResult<&str, RespValue> {
+    map(
+        complete(delimited(
+            tag("#"),
+            alt((map(tag("t"), |_| true), map(tag("f"), |_| false))),
+            crlf,
+        )),
+        RespValue::Boolean,
+    )
+    .parse(i)
+}
+
+#[test]
+fn test_boolean() {
+    assert_eq!(boolean("#t\r\n"), Ok(("", RespValue::Boolean(true))));
+    assert_eq!(boolean("#f\r\n"), Ok(("", RespValue::Boolean(false))));
+}
+
+/// RESP3 Big Numbers
+///
+/// A "(" byte followed by an arbitrary precision, CRLF terminated signed
+/// integer. The digits are kept verbatim as a `&str` since they may not fit in
+/// any native integer type.
+pub fn big_number(i: &str) -> IResult<&str, RespValue> {
+    map(
+        complete(delimited(tag("("), take_until(CRLF), crlf)),
+        RespValue::BigNumber,
+    )
+    .parse(i)
+}
+
+#[test]
+fn test_big_number() {
+    let i = "(3492890328409238509324850943850943825024385\r\n";
+    assert_eq!(
+        big_number(i),
+        Ok((
+            "",
+            RespValue::BigNumber("3492890328409238509324850943850943825024385")
+        ))
+    );
+}
+
+/// RESP3 Bulk Errors
+///
+/// Encoded exactly like a Bulk String but prefixed with "!" instead of "$";
+/// the length counts the bytes of the error message which follows, terminated
+/// by a final CRLF.
+pub fn bulk_error(i: &str) -> IResult<&str, RespValue> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("!"), isize, crlf)).parse(i)?;
+    // Bulk Errors are never null, so any negative length is invalid.
+    if len < 0 || len as usize > MAX_BULK_LENGTH {
+        return Err(length_error(i));
+    }
+    let len = len as usize;
+    // The length counts bytes, whereas nom's `take` on a `&str` counts
+    // characters, so the message is sliced by byte offset.
+    let message = i.get(..len).ok_or(nom::Err::Error(nom::error::Error::new(
+        i,
+        nom::error::ErrorKind::Eof,
+    )))?;
+    let (i, _) = crlf::<_, nom::error::Error<&str>>(&i[len..])?;
+    Ok((i, RespValue::BulkError(message)))
+}
+
+#[test]
+fn test_bulk_error() {
+    let i = "!21\r\nSYNTAX invalid syntax\r\n";
+    assert_eq!(
+        bulk_error(i),
+        Ok(("", RespValue::BulkError("SYNTAX invalid syntax")))
+    );
+}
+
+/// RESP3 Verbatim Strings
+///
+/// Encoded like a Bulk String but prefixed with "=". The payload begins with a
+/// three byte format (such as `txt` or `mkd`) followed by a ":" and then the
+/// actual text; the length prefix covers all of it.
+pub fn verbatim_string(i: &str) -> IResult<&str, RespValue> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("="), isize, crlf)).parse(i)?;
+    // Like a Bulk String, a Verbatim String is never null and is length-capped.
+    if len < 0 || len as usize > MAX_BULK_LENGTH {
+        return Err(length_error(i));
+    }
+    let (i, payload) = terminated(take(len as usize), crlf).parse(i)?;
+    // The payload is the three byte format, a ":" separator, then the text, so
+    // anything shorter than four bytes — or missing the separator — is invalid.
+    if payload.len() < 4 || payload.as_bytes()[3] != b':' {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let format = &payload[..3];
+    let text = &payload[4..]; // skip the ":" separator
+    Ok((i, RespValue::VerbatimString { format, text }))
+}
+
+#[test]
+fn test_verbatim_string() {
+    let i = "=15\r\ntxt:Some string\r\n";
+    assert_eq!(
+        verbatim_string(i),
+        Ok((
+            "",
+            RespValue::VerbatimString {
+                format: "txt",
+                text: "Some string"
+            }
+        ))
+    );
+}
+
+/// RESP3 Maps
+///
+/// A "%" byte followed by the number of elements, terminated by CRLF, and then
+/// that many RESP values read in key/value order. The element count must be
+/// even; an odd count is a protocol violation and is rejected.
+pub fn map_type(i: &str) -> IResult<&str, RespValue> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("%"), isize, crlf)).parse(i)?;
+    // A Map has no null form, so any negative length is invalid, even or odd.
+    if len < 0 || len % 2 != 0 {
+        return Err(length_error(i));
+    }
+    let mut pairs = Vec::new();
+    let mut rset = i;
+    for _ in 0..(len / 2) {
+        let (i, key) = resp3(rset)?;
+        let (i, value) = resp3(i)?;
+        rset = i;
+        pairs.push((key, value));
+    }
+    Ok((rset, RespValue::Map(pairs)))
+}
+
+#[test]
+fn test_map() {
+    use crate::resp::RespValue::*;
+
+    let i = "%4\r\n+first\r\n:1\r\n+second\r\n:2\r\n";
+    assert_eq!(
+        map_type(i),
+        Ok((
+            "",
+            Map(vec![
+                (SimpleString("first"), Integer(1)),
+                (SimpleString("second"), Integer(2)),
+            ])
+        ))
+    );
+
+    // Odd element counts are rejected.
+    assert!(map_type("%1\r\n+first\r\n").is_err());
+    // A negative even length is rejected too.
+    assert!(map_type("%-2\r\n").is_err());
+}
+
+/// RESP3 Sets
+///
+/// A "~" byte followed by the number of elements, terminated by CRLF, and then
+/// that many RESP values. Semantically an unordered collection of distinct
+/// values; structurally it parses just like an Array.
+pub fn set(i: &str) -> IResult<&str, RespValue> {
+    map(aggregate(tag("~")), RespValue::Set).parse(i)
+}
+
+#[test]
+fn test_set() {
+    use crate::resp::RespValue::*;
+
+    let i = "~3\r\n+a\r\n+b\r\n:7\r\n";
+    assert_eq!(
+        set(i),
+        Ok((
+            "",
+            Set(vec![SimpleString("a"), SimpleString("b"), Integer(7)])
+        ))
+    );
+}
+
+/// RESP3 Pushes
+///
+/// A ">" byte followed by the number of elements, terminated by CRLF, and then
+/// that many RESP values. Pushes carry out-of-band data (pub/sub messages and
+/// the like) but are framed exactly like an Array.
+pub fn push(i: &str) -> IResult<&str, RespValue> {
+    map(aggregate(tag(">")), RespValue::Push).parse(i)
+}
+
+#[test]
+fn test_push() {
+    use crate::resp::RespValue::*;
+
+    let i = ">2\r\n+message\r\n+hello\r\n";
+    assert_eq!(
+        push(i),
+        Ok(("", Push(vec![SimpleString("message"), SimpleString("hello")])))
+    );
+}
+
+/// Shared framing for the length-prefixed RESP3 aggregates (Set and Push):
+/// a one byte marker, a decimal element count, and then that many values read
+/// back through the [`resp3`] dispatcher.
+fn aggregate<'a>(
+    marker: impl Parser<&'a str, Output = &'a str, Error = nom::error::Error<&'a str>>,
+) -> impl Parser<&'a str, Output = Vec<RespValue<'a>>, Error = nom::error::Error<&'a str>> {
+    use nom::character::complete::isize;
+
+    let mut header = complete(delimited(marker, isize, crlf));
+    move |i| {
+        let (i, len) = header.parse(i)?;
+        // Sets and Pushes have no null form, so any negative length is invalid.
+        if len < 0 {
+            return Err(length_error(i));
+        }
+        let mut vec = Vec::new();
+        let mut rset = i;
+        for _ in 0..len {
+            let (i, o) = resp3(rset)?;
+            rset = i;
+            vec.push(o);
+        }
+        Ok((rset, vec))
+    }
+}
+
+/// A RESP3-aware Array parser.
+///
+/// Identical framing to [`array`], but elements recurse through [`resp3`] so
+/// that a RESP3 scalar or aggregate nested inside an Array parses correctly.
+fn array_resp3(i: &str) -> IResult<&str, RespValue> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("*"), isize, crlf)).parse(i)?;
+    if len == -1 {
+        return Ok((i, RespValue::Array(None)));
+    }
+    if len < -1 {
+        return Err(length_error(i));
+    }
+    let mut vec = Vec::new();
+    let mut rset = i;
+    for _ in 0..len {
+        let (i, o) = resp3(rset)?;
+        rset = i;
+        vec.push(o);
+    }
+    Ok((rset, RespValue::Array(Some(vec))))
+}
+
+/// Top-level RESP3 dispatcher.
+///
+/// Recognizes every RESP2 type plus the RESP3 additions by their leading byte.
+/// The aggregate types (Map, Set, Push and Array) recurse through this same
+/// dispatcher so that nested replies of any type parse correctly.
+pub fn resp3(i: &str) -> IResult<&str, RespValue> {
+    alt((
+        simple_string,
+        error,
+        integer,
+        bulk_string,
+        array_resp3,
+        null,
+        double,
+        boolean,
+        big_number,
+        bulk_error,
+        verbatim_string,
+        map_type,
+        set,
+        push,
+    ))
+    .parse(i)
+}
+
+#[test]
+fn test_resp3() {
+    use crate::resp::RespValue::*;
+
+    // A map whose values are themselves aggregates, exercising recursion.
+    let i = "%2\r\n+nested\r\n~2\r\n:1\r\n#t\r\n";
+    assert_eq!(
+        resp3(i),
+        Ok((
+            "",
+            Map(vec![(
+                SimpleString("nested"),
+                Set(vec![Integer(1), Boolean(true)])
+            )])
+        ))
+    );
+
+    // RESP3 scalars nested directly inside an Array must parse too.
+    let i = "*2\r\n#t\r\n,2.5\r\n";
+    assert_eq!(
+        resp3(i),
+        Ok(("", Array(Some(vec![Boolean(true), Double(2.5)]))))
+    );
+}
+
+impl RespValue<'_> {
+    /// Encode this value into its RESP wire representation.
+    ///
+    /// This is the inverse of [`parse_resp3`]: parsing the output of
+    /// `encode(v)` with it gives back `v` for every variant. The aggregate
+    /// types recurse, so a nested `Array`/`Map` is serialized in full.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_into(&mut buf);
+        buf
+    }
+
+    /// Append the RESP wire representation of this value to `buf`.
+    ///
+    /// Preferred over [`encode`](Self::encode) when serializing many values
+    /// back to back, since it reuses a single allocation.
+    pub fn encode_into(&self, buf: &mut Vec<u8>) {
+        fn line(buf: &mut Vec<u8>, prefix: u8, body: &str) {
+            buf.push(prefix);
+            buf.extend_from_slice(body.as_bytes());
+            buf.extend_from_slice(b"\r\n");
+        }
+        fn bulk(buf: &mut Vec<u8>, prefix: u8, body: &str) {
+            line(buf, prefix, &body.len().to_string());
+            buf.extend_from_slice(body.as_bytes());
+            buf.extend_from_slice(b"\r\n");
+        }
+
+        match self {
+            RespValue::SimpleString(s) => line(buf, b'+', s),
+            RespValue::Error(s) => line(buf, b'-', s),
+            RespValue::Integer(n) => line(buf, b':', &n.to_string()),
+            RespValue::BulkString(None) => buf.extend_from_slice(b"$-1\r\n"),
+            RespValue::BulkString(Some(s)) => bulk(buf, b'$', s),
+            RespValue::Array(None) => buf.extend_from_slice(b"*-1\r\n"),
+            RespValue::Array(Some(v)) => {
+                line(buf, b'*', &v.len().to_string());
+                for item in v {
+                    item.encode_into(buf);
+                }
+            }
+            RespValue::Null => buf.extend_from_slice(b"_\r\n"),
+            RespValue::Double(d) => {
+                let body = if d.is_infinite() {
+                    if d.is_sign_negative() { "-inf" } else { "inf" }.to_string()
+                } else if d.is_nan() {
+                    "nan".to_string()
+                } else {
+                    d.to_string()
+                };
+                line(buf, b',', &body);
+            }
+            RespValue::Boolean(b) => line(buf, b'#', if *b { "t" } else { "f" }),
+            RespValue::BigNumber(s) => line(buf, b'(', s),
+            RespValue::BulkError(s) => bulk(buf, b'!', s),
+            RespValue::VerbatimString { format, text } => {
+                bulk(buf, b'=', &format!("{format}:{text}"));
+            }
+            RespValue::Map(pairs) => {
+                // The header counts elements, so twice the number of pairs.
+                line(buf, b'%', &(pairs.len() * 2).to_string());
+                for (k, v) in pairs {
+                    k.encode_into(buf);
+                    v.encode_into(buf);
+                }
+            }
+            RespValue::Set(v) => {
+                line(buf, b'~', &v.len().to_string());
+                for item in v {
+                    item.encode_into(buf);
+                }
+            }
+            RespValue::Push(v) => {
+                line(buf, b'>', &v.len().to_string());
+                for item in v {
+                    item.encode_into(buf);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_encode() {
+    use crate::resp::RespValue::*;
+
+    assert_eq!(SimpleString("OK").encode(), b"+OK\r\n");
+    assert_eq!(Error("Error message").encode(), b"-Error message\r\n");
+    assert_eq!(Integer(1000).encode(), b":1000\r\n");
+    assert_eq!(BulkString(Some("foobar")).encode(), b"$6\r\nfoobar\r\n");
+    assert_eq!(BulkString(Some("")).encode(), b"$0\r\n\r\n");
+    assert_eq!(BulkString(None).encode(), b"$-1\r\n");
+    assert_eq!(Array(None).encode(), b"*-1\r\n");
+    assert_eq!(
+        Array(Some(vec![BulkString(Some("foo")), BulkString(Some("bar"))])).encode(),
+        b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n"
+    );
+}
+
+#[test]
+fn test_encode_round_trip() {
+    use crate::resp::RespValue::*;
+
+    let values = vec![
+        SimpleString("OK"),
+        Error("ERR bad"),
+        Integer(-42),
+        BulkString(Some("foobar")),
+        BulkString(Some("")),
+        BulkString(None),
+        Array(None),
+        Array(Some(vec![Integer(1), BulkString(Some("two")), Array(None)])),
+        Null,
+        Double(2.5),
+        Boolean(true),
+        Boolean(false),
+        BigNumber("3492890328409238509324850943850943825024385"),
+        BulkError("SYNTAX bad"),
+        VerbatimString {
+            format: "txt",
+            text: "Some string",
+        },
+        Map(vec![(SimpleString("k"), Integer(1))]),
+        Set(vec![Integer(1), Integer(2)]),
+        Push(vec![SimpleString("message"), SimpleString("hi")]),
+    ];
+
+    for value in values {
+        let encoded = value.encode();
+        let text = std::str::from_utf8(&encoded).unwrap();
+        assert_eq!(resp3(text), Ok(("", value.clone())), "round-trip of {value:?}");
+    }
+}
+
+/// A binary-safe mirror of [`RespValue`].
+///
+/// Bulk Strings in RESP are explicitly binary safe and may carry up to 512 MB
+/// of arbitrary bytes that are not valid UTF-8, so the length-prefixed bulk
+/// payload is kept as a raw `&[u8]` here. The simple (non length-prefixed)
+/// types — Simple Strings, Errors and Integers — are still required by the
+/// protocol to be UTF-8 and are validated as such, matching how the ecosystem
+/// (redis-async, redis-parser) models bulk data as `&[u8]`/`Vec<u8>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RespValueBytes<'a> {
+    SimpleString(&'a str),
+    Error(&'a str),
+    Integer(i64),
+    BulkString(Option<&'a [u8]>),
+    Array(Option<Vec<RespValueBytes<'a>>>),
+}
+
+/// Binary-safe Simple String parser. The body is still validated as UTF-8.
+pub fn simple_string_bytes(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    map(
+        complete(delimited(
+            tag("+"),
+            map_res(take_until(CRLF), std::str::from_utf8),
+            crlf,
+        )),
+        RespValueBytes::SimpleString,
+    )
+    .parse(i)
+}
+
+/// Binary-safe Error parser. The body is still validated as UTF-8.
+pub fn error_bytes(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    map(
+        complete(delimited(
+            tag("-"),
+            map_res(take_until(CRLF), std::str::from_utf8),
+            crlf,
+        )),
+        RespValueBytes::Error,
+    )
+    .parse(i)
+}
+
+/// Binary-safe Integer parser.
+pub fn integer_bytes(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::complete::i64;
+
+    map(
+        complete(delimited(tag(":"), i64, crlf)),
+        RespValueBytes::Integer,
+    )
+    .parse(i)
+}
+
+/// Binary-safe Bulk String parser.
+///
+/// Unlike [`bulk_string`], the payload is returned as a raw byte slice and may
+/// therefore hold arbitrary, non-UTF-8 data.
+pub fn bulk_string_bytes(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("$"), isize, crlf)).parse(i)?;
+    if len == -1 {
+        Ok((i, RespValueBytes::BulkString(None)))
+    } else if len < -1 || len as usize > MAX_BULK_LENGTH {
+        Err(length_error(i))
+    } else {
+        map(terminated(take(len as usize), crlf), |bytes| {
+            RespValueBytes::BulkString(Some(bytes))
+        })
+        .parse(i)
+    }
+}
+
+/// Binary-safe Array parser, recursing through the byte-oriented parsers.
+pub fn array_bytes(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::complete::isize;
+
+    let (i, len) = complete(delimited(tag("*"), isize, crlf)).parse(i)?;
+    if len == -1 {
+        return Ok((i, RespValueBytes::Array(None)));
+    }
+    if len < -1 {
+        return Err(length_error(i));
+    }
+    let mut vec = Vec::new();
+    let mut rset = i;
+    for _ in 0..len {
+        let (i, o) = alt((
+            simple_string_bytes,
+            error_bytes,
+            integer_bytes,
+            bulk_string_bytes,
+            array_bytes,
+        ))
+        .parse(rset)?;
+        rset = i;
+        vec.push(o);
+    }
+    Ok((rset, RespValueBytes::Array(Some(vec))))
+}
+
+#[test]
+fn test_bulk_string_bytes() {
+    let i = b"$6\r\nfoobar\r\n";
+    assert_eq!(
+        bulk_string_bytes(i),
+        Ok((&b""[..], RespValueBytes::BulkString(Some(&b"foobar"[..]))))
+    );
+
+    // A payload that is not valid UTF-8 round-trips unchanged.
+    let i = b"$3\r\n\x00\xff\x01\r\n";
+    assert_eq!(
+        bulk_string_bytes(i),
+        Ok((&b""[..], RespValueBytes::BulkString(Some(&[0x00, 0xff, 0x01][..]))))
+    );
+
+    let i = b"$-1\r\n";
+    assert_eq!(
+        bulk_string_bytes(i),
+        Ok((&b""[..], RespValueBytes::BulkString(None)))
+    );
+    // A negative length other than -1 is rejected, not used as a capacity.
+    assert!(bulk_string_bytes(b"$-2\r\n").is_err());
+}
+
+#[test]
+fn test_array_bytes() {
+    use crate::resp::RespValueBytes::*;
+
+    let i = b"*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n";
+    assert_eq!(
+        array_bytes(i),
+        Ok((
+            &b""[..],
+            Array(Some(vec![
+                BulkString(Some(&b"foo"[..])),
+                BulkString(Some(&b"bar"[..])),
+            ]))
+        ))
+    );
+
+    // A negative length other than -1 is rejected rather than read as an
+    // empty Array.
+    assert!(array_bytes(b"*-2\r\n").is_err());
+}
+
+impl<'a> RespValueBytes<'a> {
+    /// Build a Redis request from an argument list.
+    ///
+    /// Redis commands are sent as a RESP Array whose every element is a Bulk
+    /// String, e.g. `SET key value` becomes
+    /// `*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n`. This wraps each
+    /// argument — any `AsRef<[u8]>`, so keys and values may be arbitrary binary
+    /// data — into exactly that shape in a single call.
+    pub fn command<I, B>(args: I) -> RespValueBytes<'a>
+    where
+        I: IntoIterator<Item = &'a B>,
+        B: AsRef<[u8]> + ?Sized + 'a,
+    {
+        let args = args
+            .into_iter()
+            .map(|arg| RespValueBytes::BulkString(Some(arg.as_ref())))
+            .collect();
+        RespValueBytes::Array(Some(args))
+    }
+}
+
+/// Parse a legacy inline command.
+///
+/// Before the unified request protocol, clients could send a bare line — a
+/// command and its arguments separated by spaces and terminated by CRLF — and
+/// Redis still accepts these. Each space-separated token becomes a Bulk
+/// String, mirroring [`RespValueBytes::command`]; runs of spaces are collapsed.
+pub fn inline_command(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    let (i, line) = complete(terminated(take_until(CRLF), crlf)).parse(i)?;
+    let args = line
+        .split(|&b| b == b' ')
+        .filter(|token| !token.is_empty())
+        .map(|token| RespValueBytes::BulkString(Some(token)))
+        .collect();
+    Ok((i, RespValueBytes::Array(Some(args))))
+}
+
+#[test]
+fn test_command() {
+    use crate::resp::RespValueBytes::*;
+
+    assert_eq!(
+        RespValueBytes::command(["SET", "key", "value"]),
+        Array(Some(vec![
+            BulkString(Some(&b"SET"[..])),
+            BulkString(Some(&b"key"[..])),
+            BulkString(Some(&b"value"[..])),
+        ]))
+    );
+}
+
+#[test]
+fn test_inline_command() {
+    use crate::resp::RespValueBytes::*;
+
+    let i = b"PING\r\n";
+    assert_eq!(
+        inline_command(i),
+        Ok((&b""[..], Array(Some(vec![BulkString(Some(&b"PING"[..]))]))))
+    );
+
+    // Spaces split arguments; consecutive spaces are ignored.
+    let i = b"SET  key value\r\n";
+    assert_eq!(
+        inline_command(i),
+        Ok((
+            &b""[..],
+            Array(Some(vec![
+                BulkString(Some(&b"SET"[..])),
+                BulkString(Some(&b"key"[..])),
+                BulkString(Some(&b"value"[..])),
+            ]))
+        ))
+    );
+}
+
+/// The maximum length of a Bulk String, as mandated by the protocol: 512 MB.
+pub const MAX_BULK_LENGTH: usize = 512 * 1024 * 1024;
+
+/// A typed RESP protocol error.
+///
+/// The raw nom combinators enforce the length invariants — the 512 MB bulk cap
+/// and the rule that `-1` is the only legal negative length (see
+/// [`length_error`]) — but report any failure as nom's opaque generic error,
+/// which cannot tell a caller *why* a frame was rejected. The public
+/// [`parse`]/[`parse_resp3`] entry points map those failures into this enum,
+/// with the offending byte offset, so a server can reply with a precise
+/// protocol error.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RespError {
+    /// A Bulk String (or Bulk Error / Verbatim String) announced more than
+    /// [`MAX_BULK_LENGTH`] bytes.
+    #[error("bulk length {length} exceeds the 512 MB maximum at byte {offset}")]
+    BulkStringTooLong { length: usize, offset: usize },
+
+    /// A length prefix was negative but not the sentinel `-1`.
+    #[error("invalid length {length} at byte {offset}")]
+    InvalidLength { length: isize, offset: usize },
+
+    /// A Map was announced with an odd number of elements.
+    #[error("map has an odd element count {count} at byte {offset}")]
+    OddMapElementCount { count: isize, offset: usize },
+
+    /// A length prefix or an Integer value was not a valid integer.
+    #[error("invalid integer at byte {offset}")]
+    InvalidInteger { offset: usize },
+
+    /// The input was truncated or did not match any known type.
+    #[error("malformed or truncated input at byte {offset}")]
+    Malformed { offset: usize },
+}
+
+/// Parse a single RESP2 value from a complete buffer.
+///
+/// Returns a typed [`RespError`] — rather than nom's generic error — on
+/// malformed input, and fails if there are trailing bytes after the value.
+pub fn parse(input: &str) -> Result<RespValue, RespError> {
+    parse_with(input, false)
+}
+
+/// Parse a single RESP3 value from a complete buffer.
+///
+/// Like [`parse`], but also recognizes the RESP3 types dispatched by
+/// [`resp3`].
+pub fn parse_resp3(input: &str) -> Result<RespValue, RespError> {
+    parse_with(input, true)
+}
+
+fn parse_with(input: &str, resp3: bool) -> Result<RespValue, RespError> {
+    let (rest, value) = parse_value(input, input, resp3)?;
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(RespError::Malformed {
+            offset: input.len() - rest.len(),
+        })
+    }
+}
+
+/// Recursive descent over a full buffer. `full` is the original input, used to
+/// report absolute byte offsets; `i` is the remaining slice.
+fn parse_value<'a>(
+    full: &str,
+    i: &'a str,
+    resp3: bool,
+) -> Result<(&'a str, RespValue<'a>), RespError> {
+    let offset = full.len() - i.len();
+    let marker = i.as_bytes().first().copied();
+
+    // Adapt one of the internal nom combinators for a leaf (non length-prefixed)
+    // type, translating its opaque failure into a `Malformed` at this offset.
+    let leaf = |p: fn(&'a str) -> IResult<&'a str, RespValue<'a>>| {
+        p(i).map_err(|_| RespError::Malformed { offset })
+    };
+
+    match marker {
+        Some(b'+') => leaf(simple_string),
+        Some(b'-') => leaf(error),
+        Some(b':') => integer(i).map_err(|_| RespError::InvalidInteger { offset }),
+        Some(b'$') => {
+            let (rest, len) = read_length(full, &i[1..])?;
+            if len == -1 {
+                return Ok((rest, RespValue::BulkString(None)));
+            }
+            let (rest, data) = checked_payload(rest, len, offset)?;
+            Ok((rest, RespValue::BulkString(Some(data))))
+        }
+        Some(b'*') => {
+            let (rest, len) = read_length(full, &i[1..])?;
+            checked_length(len, offset)?;
+            if len == -1 {
+                return Ok((rest, RespValue::Array(None)));
+            }
+            let (rest, items) = parse_sequence(full, rest, len as usize, resp3)?;
+            Ok((rest, RespValue::Array(Some(items))))
+        }
+        _ if !resp3 => Err(RespError::Malformed { offset }),
+        Some(b'_') => leaf(null),
+        Some(b',') => leaf(double),
+        Some(b'#') => leaf(boolean),
+        Some(b'(') => leaf(big_number),
+        Some(b'!') => {
+            // Bulk Errors are never null, so a negative length — including the
+            // `-1` sentinel — is rejected by `checked_payload`.
+            let (rest, len) = read_length(full, &i[1..])?;
+            let (rest, data) = checked_payload(rest, len, offset)?;
+            Ok((rest, RespValue::BulkError(data)))
+        }
+        Some(b'=') => {
+            // Like a Bulk String, Verbatim Strings are length-capped; the
+            // payload is a three byte format, a ":" separator, then the text.
+            let (rest, len) = read_length(full, &i[1..])?;
+            let (rest, payload) = checked_payload(rest, len, offset)?;
+            if payload.len() < 4 || payload.as_bytes()[3] != b':' {
+                return Err(RespError::Malformed { offset });
+            }
+            let value = RespValue::VerbatimString {
+                format: &payload[..3],
+                text: &payload[4..],
+            };
+            Ok((rest, value))
+        }
+        Some(b'%') => {
+            let (rest, len) = read_length(full, &i[1..])?;
+            if len % 2 != 0 {
+                return Err(RespError::OddMapElementCount { count: len, offset });
+            }
+            checked_length(len, offset)?;
+            let mut pairs = Vec::new();
+            let mut cur = rest;
+            for _ in 0..(len / 2) {
+                let (next, key) = parse_value(full, cur, resp3)?;
+                let (next, value) = parse_value(full, next, resp3)?;
+                cur = next;
+                pairs.push((key, value));
+            }
+            Ok((cur, RespValue::Map(pairs)))
+        }
+        Some(b'~') => {
+            let (rest, len) = read_length(full, &i[1..])?;
+            checked_length(len, offset)?;
+            let (rest, items) = parse_sequence(full, rest, len as usize, resp3)?;
+            Ok((rest, RespValue::Set(items)))
+        }
+        Some(b'>') => {
+            let (rest, len) = read_length(full, &i[1..])?;
+            checked_length(len, offset)?;
+            let (rest, items) = parse_sequence(full, rest, len as usize, resp3)?;
+            Ok((rest, RespValue::Push(items)))
+        }
+        _ => Err(RespError::Malformed { offset }),
+    }
+}
+
+/// Read a CRLF-terminated decimal length, returning the remainder and value.
+fn read_length<'a>(full: &str, i: &'a str) -> Result<(&'a str, isize), RespError> {
+    let offset = full.len() - i.len();
+    let end = i
+        .find(CRLF)
+        .ok_or(RespError::Malformed { offset })?;
+    let len = i[..end]
+        .parse::<isize>()
+        .map_err(|_| RespError::InvalidInteger { offset })?;
+    Ok((&i[end + CRLF.len()..], len))
+}
+
+/// Reject any length below the `-1` sentinel.
+fn checked_length(len: isize, offset: usize) -> Result<(), RespError> {
+    if len < -1 {
+        Err(RespError::InvalidLength { length: len, offset })
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate and read a non-null, length-prefixed payload (Bulk String, Bulk
+/// Error or Verbatim String), enforcing the 512 MB cap.
+///
+/// A negative length — including the `-1` null sentinel — is rejected here;
+/// callers that permit a null value (only the Bulk String does) must handle
+/// `-1` before calling.
+fn checked_payload(i: &str, len: isize, offset: usize) -> Result<(&str, &str), RespError> {
+    if len < 0 {
+        return Err(RespError::InvalidLength { length: len, offset });
+    }
+    let len = len as usize;
+    if len > MAX_BULK_LENGTH {
+        return Err(RespError::BulkStringTooLong { length: len, offset });
+    }
+    // `get` yields `None` on an out-of-range or non-char-boundary slice rather
+    // than panicking, so malformed input surfaces as a typed error.
+    let data = i.get(..len).ok_or(RespError::Malformed { offset })?;
+    if i.get(len..len + CRLF.len()) != Some(CRLF) {
+        return Err(RespError::Malformed { offset });
+    }
+    Ok((&i[len + CRLF.len()..], data))
+}
+
+/// Parse exactly `count` consecutive values for an aggregate.
+///
+/// The count is attacker-controlled, so the vector grows lazily rather than
+/// reserving `count` up front — a bogus header such as `*999999999999` fails
+/// fast on the first missing element instead of attempting a huge allocation.
+fn parse_sequence<'a>(
+    full: &str,
+    i: &'a str,
+    count: usize,
+    resp3: bool,
+) -> Result<(&'a str, Vec<RespValue<'a>>), RespError> {
+    let mut vec = Vec::new();
+    let mut cur = i;
+    for _ in 0..count {
+        let (next, value) = parse_value(full, cur, resp3)?;
+        cur = next;
+        vec.push(value);
+    }
+    Ok((cur, vec))
+}
+
+#[test]
+fn test_parse_public() {
+    use crate::resp::RespValue::*;
+
+    assert_eq!(parse("+OK\r\n"), Ok(SimpleString("OK")));
+    assert_eq!(parse("$6\r\nfoobar\r\n"), Ok(BulkString(Some("foobar"))));
+    assert_eq!(parse("$-1\r\n"), Ok(BulkString(None)));
+    assert_eq!(
+        parse("*2\r\n:1\r\n$3\r\nfoo\r\n"),
+        Ok(Array(Some(vec![Integer(1), BulkString(Some("foo"))])))
+    );
+
+    // Trailing bytes are rejected.
+    assert_eq!(parse("+OK\r\n:1\r\n"), Err(RespError::Malformed { offset: 5 }));
+}
+
+#[test]
+fn test_parse_errors() {
+    // Length beyond the 512 MB cap.
+    assert_eq!(
+        parse("$536870913\r\n"),
+        Err(RespError::BulkStringTooLong {
+            length: MAX_BULK_LENGTH + 1,
+            offset: 0
+        })
+    );
+    // Negative length other than -1.
+    assert_eq!(
+        parse("$-2\r\n"),
+        Err(RespError::InvalidLength {
+            length: -2,
+            offset: 0
+        })
+    );
+    // Non-numeric length.
+    assert_eq!(
+        parse("$abc\r\n"),
+        Err(RespError::InvalidInteger { offset: 1 })
+    );
+    // Odd map element count.
+    assert_eq!(
+        parse_resp3("%1\r\n+a\r\n"),
+        Err(RespError::OddMapElementCount { count: 1, offset: 0 })
+    );
+    // A Verbatim String shorter than the format + separator is an error, not a
+    // panic inside the public API.
+    assert_eq!(
+        parse_resp3("=2\r\nab\r\n"),
+        Err(RespError::Malformed { offset: 0 })
+    );
+    // Bulk Errors are never null: `-1` is rejected rather than becoming "".
+    assert_eq!(
+        parse_resp3("!-1\r\n"),
+        Err(RespError::InvalidLength { length: -1, offset: 0 })
+    );
+    // A huge aggregate count must not attempt a giant allocation; it fails fast.
+    assert_eq!(
+        parse("*999999999999\r\n"),
+        Err(RespError::Malformed { offset: 15 })
+    );
+}
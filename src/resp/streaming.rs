@@ -0,0 +1,132 @@
+//! Streaming (incremental) RESP parsers.
+//!
+//! Unlike the parsers in the parent module, which wrap their input in
+//! `complete(...)` and report a short read as a hard error, these are built on
+//! `nom::bytes::streaming` and return `Err(Incomplete(Needed))` when a length
+//! prefix promises more bytes than are currently available. That is what lets
+//! a framed reader ask for more data instead of failing on a partial buffer.
+//!
+//! Like [`RespValueBytes`], the bulk payload is kept as raw bytes so the
+//! streaming path is binary safe — exactly what is needed when feeding the
+//! parser straight off a socket.
+
+use nom::IResult;
+use nom::Parser;
+use nom::branch::alt;
+use nom::bytes::streaming::tag;
+use nom::bytes::streaming::take;
+use nom::bytes::streaming::take_until;
+use nom::character::streaming::crlf;
+use nom::combinator::map;
+use nom::combinator::map_res;
+use nom::sequence::delimited;
+use nom::sequence::terminated;
+
+use crate::resp::CRLF;
+use crate::resp::MAX_BULK_LENGTH;
+use crate::resp::RespValueBytes;
+use crate::resp::length_error;
+
+/// Streaming Simple String parser. The body is still validated as UTF-8.
+pub fn simple_string(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    map(
+        delimited(tag("+"), map_res(take_until(CRLF), std::str::from_utf8), crlf),
+        RespValueBytes::SimpleString,
+    )
+    .parse(i)
+}
+
+/// Streaming Error parser. The body is still validated as UTF-8.
+pub fn error(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    map(
+        delimited(tag("-"), map_res(take_until(CRLF), std::str::from_utf8), crlf),
+        RespValueBytes::Error,
+    )
+    .parse(i)
+}
+
+/// Streaming Integer parser.
+pub fn integer(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::streaming::i64;
+
+    map(delimited(tag(":"), i64, crlf), RespValueBytes::Integer).parse(i)
+}
+
+/// Streaming Bulk String parser.
+///
+/// Returns `Incomplete` when the announced length exceeds the bytes currently
+/// buffered, so the caller can wait for the rest of the payload.
+pub fn bulk_string(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::streaming::isize;
+
+    let (i, len) = delimited(tag("$"), isize, crlf).parse(i)?;
+    if len == -1 {
+        Ok((i, RespValueBytes::BulkString(None)))
+    } else if len < -1 || len as usize > MAX_BULK_LENGTH {
+        Err(length_error(i))
+    } else {
+        map(terminated(take(len as usize), crlf), |bytes| {
+            RespValueBytes::BulkString(Some(bytes))
+        })
+        .parse(i)
+    }
+}
+
+/// Streaming Array parser, recursing through [`parse`].
+pub fn array(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    use nom::character::streaming::isize;
+
+    let (i, len) = delimited(tag("*"), isize, crlf).parse(i)?;
+    if len == -1 {
+        return Ok((i, RespValueBytes::Array(None)));
+    }
+    if len < -1 {
+        return Err(length_error(i));
+    }
+    // Grow lazily: the announced count is untrusted, so don't pre-reserve.
+    let mut vec = Vec::new();
+    let mut rset = i;
+    for _ in 0..len {
+        let (i, o) = parse(rset)?;
+        rset = i;
+        vec.push(o);
+    }
+    Ok((rset, RespValueBytes::Array(Some(vec))))
+}
+
+/// Top-level streaming dispatcher over the five RESP2 types.
+pub fn parse(i: &[u8]) -> IResult<&[u8], RespValueBytes> {
+    alt((simple_string, error, integer, bulk_string, array)).parse(i)
+}
+
+#[test]
+fn test_streaming_incomplete() {
+    use nom::Err::Incomplete;
+
+    // A bulk string whose payload has not arrived yet is reported as a need
+    // for more data rather than a hard error.
+    assert!(matches!(bulk_string(b"$6\r\nfoo"), Err(Incomplete(_))));
+    // An array whose last element is cut short is likewise incomplete.
+    assert!(matches!(parse(b"*2\r\n$3\r\nfoo"), Err(Incomplete(_))));
+
+    // A negative length other than the `-1` sentinel is a hard error, not an
+    // endless request for bytes that will never come.
+    assert!(bulk_string(b"$-2\r\n").is_err());
+    assert!(!matches!(bulk_string(b"$-2\r\n"), Err(Incomplete(_))));
+    assert!(array(b"*-2\r\n").is_err());
+}
+
+#[test]
+fn test_streaming_complete() {
+    let i = b"$6\r\nfoobar\r\n";
+    assert_eq!(
+        parse(i),
+        Ok((&b""[..], RespValueBytes::BulkString(Some(&b"foobar"[..]))))
+    );
+    // Trailing bytes of the next frame are left in the buffer.
+    let i = b"+OK\r\n:1\r\n";
+    assert_eq!(
+        parse(i),
+        Ok((&b":1\r\n"[..], RespValueBytes::SimpleString("OK")))
+    );
+}